@@ -0,0 +1,114 @@
+use eframe::egui;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+
+/// Top-level bar configuration, loaded from `quebar.toml`.
+///
+/// Every field has a serde default so a partial (or entirely missing)
+/// config file still produces the same behavior QueBar shipped with
+/// before this file existed, with one difference: workspace colors are
+/// opaque RGB, so the visible-workspace chip is solid gray rather than
+/// translucent black.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Config {
+  pub bar_height: f32,
+  pub position: [f32; 2],
+  pub background_alpha: u8,
+  pub glazewm_url: String,
+  pub time_format: String,
+  pub date_format: String,
+  pub colors: WorkspaceColors,
+  /// Battery percentage at or below which the battery module turns red
+  /// and fires a one-shot low-battery notification while discharging.
+  pub low_battery_percent: f32,
+  /// Names of modules to show on the left side of the bar, in order.
+  /// Recognized names: "workspaces", "clock", "battery".
+  pub left_modules: Vec<String>,
+  /// Same as `left_modules`, for the right side of the bar.
+  pub right_modules: Vec<String>,
+}
+
+impl Default for Config {
+  fn default() -> Self {
+    Self {
+      bar_height: 32.0,
+      position: [0.0, 0.0],
+      background_alpha: 180,
+      glazewm_url: "ws://localhost:6123".into(),
+      time_format: "%H:%M".into(),
+      date_format: "%m/%d/%Y".into(),
+      colors: WorkspaceColors::default(),
+      low_battery_percent: 15.0,
+      left_modules: vec!["workspaces".into()],
+      right_modules: vec!["battery".into(), "clock".into()],
+    }
+  }
+}
+
+/// Colors for the three workspace-chip states GlazeWM reports.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct WorkspaceColors {
+  pub focused: [u8; 3],
+  pub visible: [u8; 3],
+  pub idle: [u8; 3],
+}
+
+impl Default for WorkspaceColors {
+  fn default() -> Self {
+    Self {
+      focused: [70, 70, 180],
+      visible: [80, 80, 80],
+      idle: [160, 160, 160],
+    }
+  }
+}
+
+impl Config {
+  /// Loads `quebar.toml` from the given path, falling back to
+  /// `Config::default()` if the file is missing or fails to parse.
+  pub fn load(path: impl AsRef<Path>) -> Self {
+    std::fs::read_to_string(path)
+      .ok()
+      .and_then(|raw| toml::from_str(&raw).ok())
+      .unwrap_or_default()
+  }
+
+  /// Watches `path` on a background thread and pushes a freshly reloaded
+  /// `Config` through the returned channel on every debounced change, so
+  /// `update()` can pick it up alongside `ws_rx`/`bat_rx`.
+  pub fn watch(path: impl Into<PathBuf>, ctx: egui::Context) -> Receiver<Config> {
+    let path = path.into();
+    let (tx, rx) = channel();
+
+    std::thread::spawn(move || {
+      let (notify_tx, notify_rx) = channel();
+      let mut watcher = match RecommendedWatcher::new(notify_tx, notify::Config::default()) {
+        Ok(watcher) => watcher,
+        Err(_) => return,
+      };
+
+      if watcher.watch(&path, RecursiveMode::NonRecursive).is_err() {
+        return;
+      }
+
+      for result in &notify_rx {
+        if result.is_err() {
+          continue;
+        }
+        // Debounce the burst of events one save can trigger: reload only
+        // once no further event has arrived for 300ms.
+        while notify_rx.recv_timeout(Duration::from_millis(300)).is_ok() {}
+
+        let _ = tx.send(Config::load(&path));
+        ctx.request_repaint();
+      }
+    });
+
+    rx
+  }
+}
@@ -0,0 +1,210 @@
+use eframe::egui;
+use serde::Deserialize;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{connect, Message};
+use url::Url;
+
+/// Used when `glazewm_url` in `quebar.toml` fails to parse, so a typo
+/// leaves QueBar retrying a reachable address instead of never
+/// connecting at all.
+const DEFAULT_GLAZEWM_URL: &str = "ws://localhost:6123";
+
+/// One inbound IPC frame, for the debug inspector window: the raw text,
+/// what `messageType` (if any) it parsed as, and whether QueBar was able
+/// to act on it.
+#[derive(Clone, Debug)]
+pub struct IpcLogEntry {
+  pub timestamp: chrono::DateTime<chrono::Local>,
+  pub raw: String,
+  pub parsed_type: Option<String>,
+  pub result: Result<(), String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct GlazeEnvelope {
+  #[serde(rename = "messageType")]
+  message_type: String,
+  data: serde_json::Value,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Workspace {
+  pub name: String,
+
+  #[serde(default, alias = "hasFocus")]
+  pub focused: bool,
+
+  #[serde(default, alias = "isDisplayed")]
+  pub visible: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct WorkspacesData {
+  workspaces: Vec<Workspace>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Monitor {
+  pub id: String,
+  pub width: f32,
+  pub height: f32,
+  pub x: f32,
+  pub y: f32,
+
+  /// GlazeWM nests workspaces under their owning monitor in the container
+  /// tree rather than stamping each workspace with a monitor id, so this
+  /// is how a per-monitor bar finds its own workspaces.
+  #[serde(default, alias = "children")]
+  pub workspaces: Vec<Workspace>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct MonitorsData {
+  monitors: Vec<Monitor>,
+}
+
+/// Live snapshot of GlazeWM's workspace/monitor topology, kept current by
+/// a background reader thread. Cheap to clone and safe to read each frame
+/// from as many viewports as there are monitors.
+#[derive(Clone)]
+pub struct GlazeClient {
+  workspaces: Arc<Mutex<Vec<Workspace>>>,
+  monitors: Arc<Mutex<Vec<Monitor>>>,
+  command_tx: Sender<String>,
+}
+
+impl GlazeClient {
+  /// Spawns the GlazeWM WebSocket reader thread and returns a handle onto
+  /// the workspace/monitor state it keeps updated, plus a way to send
+  /// commands back over the same socket.
+  pub fn spawn(ctx: egui::Context, url: String) -> (Self, Receiver<IpcLogEntry>) {
+    let workspaces = Arc::new(Mutex::new(Vec::new()));
+    let monitors = Arc::new(Mutex::new(Vec::new()));
+    let workspaces_thread = workspaces.clone();
+    let monitors_thread = monitors.clone();
+    let (command_tx, command_rx) = channel::<String>();
+    let (inspector_tx, inspector_rx) = channel::<IpcLogEntry>();
+
+    let url = match Url::parse(&url) {
+      Ok(url) => url,
+      Err(err) => {
+        // A bad glazewm_url would otherwise panic the reader thread and
+        // leave QueBar silently without workspaces/monitors forever - log
+        // it to the inspector and fall back to the default instead.
+        let _ = inspector_tx.send(IpcLogEntry {
+          timestamp: chrono::Local::now(),
+          raw: url,
+          parsed_type: None,
+          result: Err(format!("invalid glazewm_url, falling back to {DEFAULT_GLAZEWM_URL}: {err}")),
+        });
+        Url::parse(DEFAULT_GLAZEWM_URL).unwrap()
+      }
+    };
+
+    std::thread::spawn(move || {
+      loop {
+        match connect(url.as_str()) {
+          Ok((mut socket, _)) => {
+            // Reads time out after 100ms so pending outgoing commands get a
+            // chance to drain between inbound frames.
+            if let MaybeTlsStream::Plain(stream) = socket.get_ref() {
+              let _ = stream.set_read_timeout(Some(std::time::Duration::from_millis(100)));
+            }
+
+            let _ = socket.send(Message::Text("sub -e workspace_activated".into()));
+            let _ = socket.send(Message::Text("sub -e focus_changed".into()));
+            let _ = socket.send(Message::Text("sub -e monitor_added".into()));
+            let _ = socket.send(Message::Text("sub -e focused_monitor_changed".into()));
+            let _ = socket.send(Message::Text("query workspaces".into()));
+            let _ = socket.send(Message::Text("query monitors".into()));
+
+            loop {
+              while let Ok(command) = command_rx.try_recv() {
+                let _ = socket.send(Message::Text(command));
+              }
+
+              match socket.read() {
+                Ok(msg) => {
+                  if let Message::Text(text) = msg {
+                    let mut parsed_type = None;
+                    let result: Result<(), String>;
+
+                    if let Ok(envelope) = serde_json::from_str::<GlazeEnvelope>(&text) {
+                      parsed_type = Some(envelope.message_type.clone());
+
+                      result = match envelope.message_type.as_str() {
+                        "client_response" | "query_response" => {
+                          if envelope.data.get("subscriptionId").is_some() {
+                            Ok(())
+                          } else if let Ok(d) = serde_json::from_value::<WorkspacesData>(envelope.data.clone()) {
+                            *workspaces_thread.lock().unwrap() = d.workspaces;
+                            ctx.request_repaint();
+                            Ok(())
+                          } else if let Ok(d) = serde_json::from_value::<MonitorsData>(envelope.data) {
+                            *monitors_thread.lock().unwrap() = d.monitors;
+                            ctx.request_repaint();
+                            Ok(())
+                          } else {
+                            Err("data matched neither WorkspacesData nor MonitorsData".into())
+                          }
+                        }
+                        "event" | "subscribed_event" | "event_subscription" => {
+                          let _ = socket.send(Message::Text("query workspaces".into()));
+                          let _ = socket.send(Message::Text("query monitors".into()));
+                          Ok(())
+                        }
+                        other => Err(format!("unhandled message_type \"{other}\"")),
+                      };
+                    } else {
+                      result = Err("failed to deserialize GlazeEnvelope".into());
+                    }
+
+                    let _ = inspector_tx.send(IpcLogEntry {
+                      timestamp: chrono::Local::now(),
+                      raw: text,
+                      parsed_type,
+                      result,
+                    });
+                  }
+                }
+                // Unix reports a read timeout as WouldBlock, Windows as TimedOut.
+                Err(tungstenite::Error::Io(ref e))
+                  if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) =>
+                {
+                  continue
+                }
+                Err(_) => break,
+              }
+            }
+          }
+          Err(_) => std::thread::sleep(std::time::Duration::from_secs(2)),
+        }
+      }
+    });
+
+    (
+      Self {
+        workspaces,
+        monitors,
+        command_tx,
+      },
+      inspector_rx,
+    )
+  }
+
+  pub fn workspaces(&self) -> Vec<Workspace> {
+    self.workspaces.lock().unwrap().clone()
+  }
+
+  pub fn monitors(&self) -> Vec<Monitor> {
+    self.monitors.lock().unwrap().clone()
+  }
+
+  /// Sends a GlazeWM command (e.g. `"command focus --workspace 1"`) back
+  /// over the IPC socket.
+  pub fn send_command(&self, command: impl Into<String>) {
+    let _ = self.command_tx.send(command.into());
+  }
+}
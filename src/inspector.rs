@@ -0,0 +1,89 @@
+use eframe::egui;
+use std::sync::mpsc::Receiver;
+
+use crate::glaze::IpcLogEntry;
+
+const MAX_ENTRIES: usize = 500;
+
+/// Debug window showing a live, timestamped log of every raw GlazeWM IPC
+/// frame, what `messageType` it parsed as, and whether QueBar could act on
+/// it. Toggled with F9 or started open via `--inspect`.
+pub struct Inspector {
+  rx: Receiver<IpcLogEntry>,
+  entries: Vec<IpcLogEntry>,
+  open: bool,
+  paused: bool,
+  filter: String,
+}
+
+impl Inspector {
+  pub fn new(rx: Receiver<IpcLogEntry>, open_on_start: bool) -> Self {
+    Self {
+      rx,
+      entries: Vec::new(),
+      open: open_on_start,
+      paused: false,
+      filter: String::new(),
+    }
+  }
+
+  /// Drains pending log entries from the reader thread. Returns true if
+  /// anything new arrived (so the caller knows to repaint).
+  pub fn poll(&mut self) -> bool {
+    let mut updated = false;
+    while let Ok(entry) = self.rx.try_recv() {
+      updated = true;
+      if self.paused {
+        continue;
+      }
+      self.entries.push(entry);
+      if self.entries.len() > MAX_ENTRIES {
+        self.entries.remove(0);
+      }
+    }
+    updated
+  }
+
+  pub fn toggle(&mut self) {
+    self.open = !self.open;
+  }
+
+  pub fn show(&mut self, ctx: &egui::Context) {
+    if !self.open {
+      return;
+    }
+
+    let mut open = self.open;
+    egui::Window::new("QueBar Inspector")
+      .open(&mut open)
+      .default_width(560.0)
+      .default_height(360.0)
+      .show(ctx, |ui| {
+        ui.horizontal(|ui| {
+          ui.label("Filter:");
+          ui.text_edit_singleline(&mut self.filter);
+          ui.checkbox(&mut self.paused, "Pause");
+          if ui.button("Clear").clicked() {
+            self.entries.clear();
+          }
+        });
+        ui.separator();
+
+        egui::ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
+          for entry in self.entries.iter().filter(|e| self.filter.is_empty() || e.raw.contains(&self.filter)) {
+            let parsed = entry.parsed_type.as_deref().unwrap_or("<unparsed>");
+            let status = match &entry.result {
+              Ok(()) => "ok".to_string(),
+              Err(reason) => format!("error: {reason}"),
+            };
+
+            ui.label(format!("[{}] {}  {}", entry.timestamp.format("%H:%M:%S%.3f"), parsed, status));
+            ui.label(egui::RichText::new(&entry.raw).weak().small());
+            ui.separator();
+          }
+        });
+      });
+
+    self.open = open;
+  }
+}
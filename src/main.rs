@@ -1,222 +1,329 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use eframe::egui;
-use serde::Deserialize;
-use std::sync::mpsc::{channel, Receiver};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
-use tungstenite::{connect, Message};
-use url::Url;
-
-#[derive(Deserialize, Debug, Clone)]
-struct GlazeEnvelope {
-  #[serde(rename = "messageType")]
-  message_type: String,
-  data: serde_json::Value, 
-}
-
-#[derive(Deserialize, Debug, Clone)]
-struct Workspace {
-  name: String,
+mod config;
+mod glaze;
+mod inspector;
+mod modules;
 
-  #[serde(default, alias = "hasFocus")]
-  focused: bool,
+use config::{Config, WorkspaceColors};
+use eframe::egui;
+use glaze::{GlazeClient, Monitor, Workspace};
+use inspector::Inspector;
+use modules::battery::BatteryModule;
+use modules::clock::ClockModule;
+use modules::workspaces::WorkspacesModule;
+use modules::Module;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::mpsc::Receiver;
 
-  #[serde(default, alias = "isDisplayed")]
-  visible: bool,
-}
-
-#[derive(Deserialize, Debug, Clone)]
-struct WorkspacesData {
-  workspaces: Vec<Workspace>,
-}
+const CONFIG_PATH: &str = "quebar.toml";
 
 fn main() -> eframe::Result<()> {
+  let config = Config::load(CONFIG_PATH);
+  let inspect_on_start = std::env::args().any(|arg| arg == "--inspect");
+
   let native_options = eframe::NativeOptions {
     viewport: egui::ViewportBuilder::default()
       .with_decorations(false)
       .with_always_on_top()
       .with_taskbar(false)
-      .with_inner_size([1920.0, 32.0])
-      .with_position([0.0, 0.0]),
+      .with_inner_size([1920.0, config.bar_height])
+      .with_position(config.position),
       ..Default::default()
   };
 
   eframe::run_native(
     "QueBar",
     native_options,
-    Box::new(|cc| {
+    Box::new(move |cc| {
       let ctx = cc.egui_ctx.clone();
-      let ctx_bat = cc.egui_ctx.clone();
-
-      let (ws_tx, ws_rx) = channel();
-      let (bat_tx, bat_rx) = channel();
-      let repaint_signal = Arc::new(AtomicBool::new(false));
-      let repaint_signal_ws = repaint_signal.clone();
-
-      std::thread::spawn(move || {
-        let url = Url::parse("ws://localhost:6123").unwrap();
-        loop {
-          match connect(url.as_str()) {
-            Ok((mut socket, _)) => {
-              let _ = socket.send(Message::Text("sub -e workspace_activated".into()));
-              let _ = socket.send(Message::Text("sub -e focus_changed".into()));
-              let _ = socket.send(Message::Text("query workspaces".into()));
-
-              loop {
-                match socket.read() {
-                  Ok(msg) => {
-                    if let Message::Text(text) = msg {
-                      if let Ok(envelope) = serde_json::from_str::<GlazeEnvelope>(&text) {
-                        match envelope.message_type.as_str() {
-                          "client_response" | "query_response" => {
-                            if envelope.data.get("subscriptionId").is_some() { continue; }
-
-                            if let Ok(d) = serde_json::from_value::<WorkspacesData>(envelope.data) {
-                              let _ = ws_tx.send(d.workspaces);
-                              repaint_signal_ws.store(true, Ordering::Relaxed); 
-                            }
-                          }
-                          "event" | "subscribed_event" | "event_subscription" => {
-                            let _ = socket.send(Message::Text("query workspaces".into()));
-                          }
-                          _ => {}
-                        }
-                      }
-                    }
-                  }
-                  Err(_) => break,
-                }
-              }
-            }
-            Err(_) => std::thread::sleep(std::time::Duration::from_secs(2)),
-          }
-        }
-      });
+      let (glaze, inspector_rx) = GlazeClient::spawn(ctx.clone(), config.glazewm_url.clone());
+      let inspector = Inspector::new(inspector_rx, inspect_on_start);
+      let config_rx = Config::watch(CONFIG_PATH, ctx.clone());
 
-      std::thread::spawn(move || {
-        loop {
-          if repaint_signal.swap(false, Ordering::Relaxed) {
-            ctx.request_repaint();
-          }
-          std::thread::sleep(std::time::Duration::from_millis(100));
-        }
-      });
-    
-      std::thread::spawn(move || {
-        let manager = battery::Manager::new().ok();
-        loop {
-          if let Some(ref mgr) = manager {
-            if let Ok(mut bats) = mgr.batteries() {
-              if let Some(Ok(bat)) = bats.next() {
-                let pct = bat.state_of_charge().get::<battery::units::ratio::percent>();
-                let _ = bat_tx.send(format!("{:.0}%", pct));
-                ctx_bat.request_repaint(); // <--- WAKE UP UI!
-              }
-            }
-          }
-          std::thread::sleep(std::time::Duration::from_secs(60));
-        }
-      });
+      let battery = Rc::new(RefCell::new(BatteryModule::spawn(ctx.clone(), config.low_battery_percent)));
+      let clock = Rc::new(RefCell::new(ClockModule::new(config.time_format.clone(), config.date_format.clone())));
 
-      Ok(Box::new(MyTaskbar::new(ws_rx, bat_rx)))
+      Ok(Box::new(MyTaskbar::new(config, glaze, battery, clock, inspector, config_rx)))
     }),
     )
 }
 
+type SharedModule = Rc<RefCell<dyn Module>>;
+
+/// One notch of a typical mouse wheel reports a `raw_scroll_delta` of
+/// around 20-50 depending on platform; past this accumulated magnitude we
+/// treat it as a deliberate gesture and cycle the focused workspace.
+const SCROLL_CYCLE_THRESHOLD: f32 = 50.0;
+
+/// One monitor's bar: a left and right module list, walked in order every
+/// frame. `WorkspacesModule` is unique per bar; `clock`/`battery` are
+/// shared single instances cloned into whichever side of every bar the
+/// config names them on.
+struct Bar {
+  left: Vec<SharedModule>,
+  right: Vec<SharedModule>,
+  scroll_accum: f32,
+}
+
+/// Builds one side's module list from config names, in order, so modules
+/// can be enabled, reordered, or omitted purely by editing `quebar.toml`.
+/// Unrecognized names are skipped. `clock`/`battery` are shared instances;
+/// `workspaces` gets a fresh module per bar.
+fn build_modules(
+  names: &[String],
+  colors: &WorkspaceColors,
+  glaze: &GlazeClient,
+  clock: &Rc<RefCell<ClockModule>>,
+  battery: &Rc<RefCell<BatteryModule>>,
+) -> Vec<SharedModule> {
+  names
+    .iter()
+    .filter_map(|name| match name.as_str() {
+      "workspaces" => {
+        Some(Rc::new(RefCell::new(WorkspacesModule::new(colors.clone(), glaze.clone()))) as SharedModule)
+      }
+      "clock" => Some(clock.clone() as SharedModule),
+      "battery" => Some(battery.clone() as SharedModule),
+      _ => None,
+    })
+    .collect()
+}
+
 struct MyTaskbar {
-  date: String,
-  time: String,
-  battery_level: String,
+  config: Config,
+  config_rx: Receiver<Config>,
+  restart_notice: bool,
+  glaze: GlazeClient,
+  battery: Rc<RefCell<BatteryModule>>,
+  clock: Rc<RefCell<ClockModule>>,
+  inspector: Inspector,
   workspaces: Vec<Workspace>,
-  ws_rx: Receiver<Vec<Workspace>>,
-  bat_rx: Receiver<String>,
+  monitors: Vec<Monitor>,
+  bars: HashMap<String, Bar>,
 }
 
 impl MyTaskbar {
-  fn new(ws_rx: Receiver<Vec<Workspace>>, bat_rx: Receiver<String>) -> Self {
+  fn new(
+    config: Config,
+    glaze: GlazeClient,
+    battery: Rc<RefCell<BatteryModule>>,
+    clock: Rc<RefCell<ClockModule>>,
+    inspector: Inspector,
+    config_rx: Receiver<Config>,
+  ) -> Self {
     Self {
-      date: String::new(),
-      time: String::new(),
-      battery_level: "100%".into(),
+      config,
+      config_rx,
+      restart_notice: false,
+      glaze,
+      battery,
+      clock,
+      inspector,
       workspaces: Vec::new(),
-      ws_rx,
-      bat_rx,
+      monitors: Vec::new(),
+      bars: HashMap::new(),
     }
   }
-}
 
-impl eframe::App for MyTaskbar {
-  fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-    let mut repaint_needed = false;
+  /// Draws one bar's contents, per `Config::left_modules`/`right_modules`,
+  /// into whichever viewport `ctx` currently refers to.
+  fn render_bar(&mut self, ctx: &egui::Context, monitor_id: &str, workspaces: Vec<Workspace>) {
+    let colors = &self.config.colors;
+    let glaze = &self.glaze;
+    let battery = &self.battery;
+    let clock = &self.clock;
+    let left_modules = &self.config.left_modules;
+    let right_modules = &self.config.right_modules;
 
-    while let Ok(ws) = self.ws_rx.try_recv() {
-      self.workspaces = ws;
-      repaint_needed = true;
-    }
+    let bar = self.bars.entry(monitor_id.to_string()).or_insert_with(|| Bar {
+      left: build_modules(left_modules, colors, glaze, clock, battery),
+      right: build_modules(right_modules, colors, glaze, clock, battery),
+      scroll_accum: 0.0,
+    });
 
-    while let Ok(bat) = self.bat_rx.try_recv() {
-      self.battery_level = bat;
-      repaint_needed = true;
-    }
-
-    let now = chrono::Local::now();
-    let new_time = now.format("%H:%M").to_string();
-    let new_date = now.format("%m/%d/%Y").to_string();
-
-    if new_time != self.time || new_date != self.date {
-      self.time = new_time;
-      self.date = new_date;
-      repaint_needed = true;
+    for module in bar.left.iter().chain(&bar.right) {
+      module.borrow_mut().set_workspaces(&workspaces);
     }
 
     let panel_frame = egui::Frame::NONE
-      .fill(egui::Color32::from_black_alpha(180))
+      .fill(egui::Color32::from_black_alpha(self.config.background_alpha))
       .inner_margin(5.0);
 
-    egui::TopBottomPanel::top("taskbar_panel")
+    let panel_resp = egui::TopBottomPanel::top("taskbar_panel")
       .frame(panel_frame)
       .show(ctx, |ui| {
         ui.horizontal(|ui| {
           ui.visuals_mut().override_text_color = Some(egui::Color32::WHITE);
 
           ui.separator();
-          ui.label("ðŸ“ QueBar");
+          ui.label("📁 QueBar");
           ui.separator();
-          // In your update loop:
-          for ws in &self.workspaces {
-            // Update to use the new fields .focused and .visible
-            let (text_color, bg_color) = match (ws.focused, ws.visible) {
-              (true, _) => (egui::Color32::WHITE, egui::Color32::from_rgb(70, 70, 180)),
-              (false, true) => (egui::Color32::LIGHT_GRAY, egui::Color32::from_black_alpha(80)),
-              _ => (egui::Color32::GRAY, egui::Color32::TRANSPARENT),
-            };
-
-            let _resp = egui::Frame::NONE
-              .fill(bg_color)
-              .corner_radius(4) // FIXED: Replaced .rounding(4.0)
-              .inner_margin(egui::Margin::symmetric(10, 2))
-              .show(ui, |ui| ui.colored_label(text_color, &ws.name))
-              .response;
+
+          for module in &bar.left {
+            module.borrow_mut().render(ui);
           }
-          ui.separator();
+
           ui.with_layout(egui::Layout::right_to_left(egui::Align::Max), |ui| {
-            ui.separator();
-            ui.label(format!("ðŸ”‹ {}  ", &self.battery_level));
-            ui.separator();
-            ui.label(&self.time);
-            ui.separator();
-            ui.label(&self.date);
-            ui.separator();
+            for module in &bar.right {
+              module.borrow_mut().render(ui);
+            }
+          });
+        });
+      })
+      .response;
+
+    // Scrolling anywhere on the bar's background cycles the focused
+    // workspace, same as clicking a chip but without aiming for one.
+    // `raw_scroll_delta` (rather than the smoothed/decaying variant) is
+    // accumulated across frames; each time the total reaches
+    // `SCROLL_CYCLE_THRESHOLD` one focus change is sent and the total
+    // resets, so a notch smaller than the threshold takes effect only
+    // together with the next ones.
+    let hovered = ctx.input(|i| i.pointer.hover_pos().is_some_and(|pos| panel_resp.rect.contains(pos)));
+    if hovered {
+      let raw_scroll = ctx.input(|i| i.raw_scroll_delta.y);
+      if raw_scroll != 0.0 {
+        bar.scroll_accum += raw_scroll;
+      }
+    }
+
+    if bar.scroll_accum >= SCROLL_CYCLE_THRESHOLD {
+      self.glaze.send_command("command focus --prev-workspace");
+      bar.scroll_accum = 0.0;
+    } else if bar.scroll_accum <= -SCROLL_CYCLE_THRESHOLD {
+      self.glaze.send_command("command focus --next-workspace");
+      bar.scroll_accum = 0.0;
+    }
+  }
+}
+
+impl eframe::App for MyTaskbar {
+  fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    let mut repaint_needed = false;
+
+    while let Ok(new_config) = self.config_rx.try_recv() {
+      // The root viewport's position comes only from `NativeOptions`,
+      // built once at startup, and so does its height until GlazeWM
+      // reports monitors; a changed `bar_height` or `position` raises the
+      // restart notice.
+      if new_config.bar_height != self.config.bar_height || new_config.position != self.config.position {
+        self.restart_notice = true;
+      }
+
+      // Modules each keep their own copy of whatever config fields they
+      // render from, so a reload has to be pushed into them explicitly.
+      self.clock.borrow_mut().set_formats(new_config.time_format.clone(), new_config.date_format.clone());
+      self.battery.borrow_mut().set_low_battery_percent(new_config.low_battery_percent);
+      for bar in self.bars.values() {
+        for module in bar.left.iter().chain(&bar.right) {
+          module.borrow_mut().set_colors(new_config.colors.clone());
+        }
+      }
+
+      // Module lists are only built once per bar in render_bar's
+      // or_insert_with, so a reordered/enabled/disabled module list has
+      // to drop the existing bars to be picked up.
+      if new_config.left_modules != self.config.left_modules || new_config.right_modules != self.config.right_modules {
+        self.bars.clear();
+      }
+
+      self.config = new_config;
+      repaint_needed = true;
+    }
+
+    let workspaces = self.glaze.workspaces();
+    if workspaces != self.workspaces {
+      self.workspaces = workspaces;
+      repaint_needed = true;
+    }
+
+    let monitors = self.glaze.monitors();
+    if monitors != self.monitors {
+      self.monitors = monitors;
+      repaint_needed = true;
+    }
+
+    if self.battery.borrow_mut().poll() {
+      repaint_needed = true;
+    }
+    if self.clock.borrow_mut().poll() {
+      repaint_needed = true;
+    }
+    if self.inspector.poll() {
+      repaint_needed = true;
+    }
+
+    if ctx.input(|i| i.key_pressed(egui::Key::F9)) {
+      self.inspector.toggle();
+    }
+    self.inspector.show(ctx);
+
+    if self.restart_notice {
+      egui::Area::new(egui::Id::new("restart_toast"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-8.0, -8.0))
+        .show(ctx, |ui| {
+          egui::Frame::popup(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+              ui.label("Restart QueBar to apply the new size/position.");
+              if ui.button("Dismiss").clicked() {
+                self.restart_notice = false;
+              }
+            });
           });
         });
-      });
+    }
+
+    if self.monitors.is_empty() {
+      // GlazeWM hasn't answered `query monitors` yet (or doesn't support
+      // it) - fall back to one unfiltered bar on the root viewport.
+      let workspaces = self.workspaces.clone();
+      self.render_bar(ctx, "default", workspaces);
+    } else {
+      let monitors = self.monitors.clone();
+      for (index, monitor) in monitors.iter().enumerate() {
+        // GlazeWM nests workspaces under their owning monitor rather than
+        // stamping each workspace with a monitor id, so membership comes
+        // straight from the monitor's own `workspaces`.
+        let workspaces = monitor.workspaces.clone();
+
+        if index == 0 {
+          // The root viewport was sized/positioned from `Config` at
+          // startup, before GlazeWM's monitor topology was known - move
+          // it onto the first monitor GlazeWM reports now that we have it.
+          ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::vec2(monitor.width, self.config.bar_height)));
+          ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(egui::pos2(monitor.x, monitor.y)));
+          self.render_bar(ctx, &monitor.id, workspaces);
+        } else {
+          let viewport_id = egui::ViewportId::from_hash_of(&monitor.id);
+          let builder = egui::ViewportBuilder::default()
+            .with_title("QueBar")
+            .with_decorations(false)
+            .with_always_on_top()
+            .with_taskbar(false)
+            .with_inner_size([monitor.width, self.config.bar_height])
+            .with_position([monitor.x, monitor.y]);
+
+          let monitor_id = monitor.id.clone();
+          ctx.show_viewport_immediate(viewport_id, builder, |ctx, _class| {
+            self.render_bar(ctx, &monitor_id, workspaces.clone());
+          });
+        }
+      }
+    }
 
     if repaint_needed {
-      ctx.request_repaint(); 
+      ctx.request_repaint();
     }
-    let seconds_until_next_minute = 60 - ((now.timestamp() as u64) % 60);
 
-    ctx.request_repaint_after(std::time::Duration::from_secs(seconds_until_next_minute));
+    let repaint_after = [self.battery.borrow().repaint_hint(), self.clock.borrow().repaint_hint()]
+      .into_iter()
+      .flatten()
+      .min()
+      .unwrap_or(std::time::Duration::from_secs(60));
+
+    ctx.request_repaint_after(repaint_after);
   }
 }
@@ -0,0 +1,150 @@
+use eframe::egui;
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::Module;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChargeState {
+  Charging,
+  Discharging,
+  Full,
+  Unknown,
+}
+
+#[derive(Debug, Clone)]
+struct BatteryStatus {
+  percent: f32,
+  state: ChargeState,
+  time_remaining: Option<Duration>,
+}
+
+impl Default for BatteryStatus {
+  fn default() -> Self {
+    Self {
+      percent: 100.0,
+      state: ChargeState::Unknown,
+      time_remaining: None,
+    }
+  }
+}
+
+/// Shows battery percentage, charging state, and an estimated time
+/// remaining, refreshed from a background polling thread owned by this
+/// module. Turns red and fires a one-shot OS notification when crossing
+/// below `low_battery_percent` while discharging.
+pub struct BatteryModule {
+  status: BatteryStatus,
+  rx: Receiver<BatteryStatus>,
+  low_battery_percent: Arc<Mutex<f32>>,
+  notified_low: bool,
+}
+
+impl BatteryModule {
+  /// Spawns the polling thread and returns a module wired to its updates.
+  pub fn spawn(ctx: egui::Context, low_battery_percent: f32) -> Self {
+    let (tx, rx) = channel();
+    let low_battery_percent = Arc::new(Mutex::new(low_battery_percent));
+    let low_battery_percent_thread = low_battery_percent.clone();
+
+    std::thread::spawn(move || loop {
+      let mut next_poll = Duration::from_secs(60);
+
+      if let Some(status) = read_battery_status() {
+        let low_battery_percent = *low_battery_percent_thread.lock().unwrap();
+        if status.state == ChargeState::Discharging && status.percent <= low_battery_percent {
+          // Keep the time-remaining estimate fresh while it matters most.
+          next_poll = Duration::from_secs(15);
+        }
+
+        let _ = tx.send(status);
+        ctx.request_repaint();
+      }
+
+      std::thread::sleep(next_poll);
+    });
+
+    Self {
+      status: BatteryStatus::default(),
+      rx,
+      low_battery_percent,
+      notified_low: false,
+    }
+  }
+
+  /// Applies a new low-battery threshold from a reloaded `Config`, shared
+  /// with the polling thread so its near-empty fast-poll interval honors
+  /// it too.
+  pub fn set_low_battery_percent(&mut self, percent: f32) {
+    *self.low_battery_percent.lock().unwrap() = percent;
+  }
+}
+
+fn read_battery_status() -> Option<BatteryStatus> {
+  let manager = battery::Manager::new().ok()?;
+  let mut batteries = manager.batteries().ok()?;
+  let bat = batteries.next()?.ok()?;
+
+  let percent = bat.state_of_charge().get::<battery::units::ratio::percent>();
+  let state = match bat.state() {
+    battery::State::Charging => ChargeState::Charging,
+    battery::State::Discharging => ChargeState::Discharging,
+    battery::State::Full => ChargeState::Full,
+    _ => ChargeState::Unknown,
+  };
+  let time_remaining = match state {
+    ChargeState::Charging => bat.time_to_full(),
+    ChargeState::Discharging => bat.time_to_empty(),
+    _ => None,
+  }
+  .map(|t| Duration::from_secs_f64(t.get::<battery::units::time::second>().max(0.0)));
+
+  Some(BatteryStatus { percent, state, time_remaining })
+}
+
+impl Module for BatteryModule {
+  fn render(&mut self, ui: &mut egui::Ui) {
+    ui.separator();
+
+    let glyph = match self.status.state {
+      ChargeState::Charging => "⚡",
+      ChargeState::Full => "🔌",
+      _ => "🔋",
+    };
+
+    let low_battery_percent = *self.low_battery_percent.lock().unwrap();
+    let low = self.status.state == ChargeState::Discharging && self.status.percent <= low_battery_percent;
+    let color = if low { egui::Color32::from_rgb(220, 60, 60) } else { egui::Color32::WHITE };
+
+    let resp = ui.colored_label(color, format!("{glyph} {:.0}%  ", self.status.percent));
+
+    if let Some(remaining) = self.status.time_remaining {
+      let mins = remaining.as_secs() / 60;
+      let verb = if self.status.state == ChargeState::Charging { "until full" } else { "remaining" };
+      resp.on_hover_text(format!("{}h {:02}m {verb}", mins / 60, mins % 60));
+    }
+  }
+
+  fn poll(&mut self) -> bool {
+    let mut updated = false;
+    let low_battery_percent = *self.low_battery_percent.lock().unwrap();
+    while let Ok(status) = self.rx.try_recv() {
+      let now_low = status.state == ChargeState::Discharging && status.percent <= low_battery_percent;
+
+      if now_low && !self.notified_low {
+        let _ = notify_rust::Notification::new()
+          .summary("Low battery")
+          .body(&format!("{:.0}% remaining", status.percent))
+          .show();
+        self.notified_low = true;
+      } else if !now_low {
+        self.notified_low = false;
+      }
+
+      self.status = status;
+      updated = true;
+    }
+    updated
+  }
+}
@@ -0,0 +1,58 @@
+use eframe::egui;
+
+use super::Module;
+
+/// Shows the current time and date, formatted per `Config::time_format` /
+/// `Config::date_format`.
+pub struct ClockModule {
+  time_format: String,
+  date_format: String,
+  time: String,
+  date: String,
+}
+
+impl ClockModule {
+  pub fn new(time_format: String, date_format: String) -> Self {
+    Self {
+      time_format,
+      date_format,
+      time: String::new(),
+      date: String::new(),
+    }
+  }
+
+  /// Applies new format strings from a reloaded `Config`; `poll` picks up
+  /// the change on its next tick.
+  pub fn set_formats(&mut self, time_format: String, date_format: String) {
+    self.time_format = time_format;
+    self.date_format = date_format;
+  }
+}
+
+impl Module for ClockModule {
+  fn render(&mut self, ui: &mut egui::Ui) {
+    ui.separator();
+    ui.label(&self.time);
+    ui.separator();
+    ui.label(&self.date);
+  }
+
+  fn poll(&mut self) -> bool {
+    let now = chrono::Local::now();
+    let new_time = now.format(&self.time_format).to_string();
+    let new_date = now.format(&self.date_format).to_string();
+
+    if new_time != self.time || new_date != self.date {
+      self.time = new_time;
+      self.date = new_date;
+      true
+    } else {
+      false
+    }
+  }
+
+  fn repaint_hint(&self) -> Option<std::time::Duration> {
+    let seconds_until_next_minute = 60 - (chrono::Local::now().timestamp() as u64 % 60);
+    Some(std::time::Duration::from_secs(seconds_until_next_minute))
+  }
+}
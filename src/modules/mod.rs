@@ -0,0 +1,39 @@
+use eframe::egui;
+
+use crate::config::WorkspaceColors;
+use crate::glaze::Workspace;
+
+pub mod battery;
+pub mod clock;
+pub mod workspaces;
+
+/// A single bar segment. Each monitor's `Bar` owns a left and a right
+/// `Vec<Rc<RefCell<dyn Module>>>` and walks them in order each frame, so
+/// modules can be enabled, reordered, or omitted purely by how they're
+/// assembled, and shared instances (clock, battery) can sit in more than
+/// one bar at once.
+pub trait Module {
+  /// Draws this module's content into the current `ui.horizontal` layout.
+  fn render(&mut self, ui: &mut egui::Ui);
+
+  /// Drains whatever arrived on this module's background channel since the
+  /// last frame. Returns true if new data means a repaint is warranted.
+  fn poll(&mut self) -> bool {
+    false
+  }
+
+  /// How soon this module wants to repaint even without new data (e.g. the
+  /// clock ticking over a minute boundary). `None` means "no opinion".
+  fn repaint_hint(&self) -> Option<std::time::Duration> {
+    None
+  }
+
+  /// Replaces the workspace set a module renders. Only `WorkspacesModule`
+  /// acts on this; it's on the trait so `render_bar` can hand workspaces to
+  /// whatever sits in a bar's module list without knowing its concrete type.
+  fn set_workspaces(&mut self, _workspaces: &[Workspace]) {}
+
+  /// Applies a reloaded `Config`'s workspace colors. Only `WorkspacesModule`
+  /// acts on this; same rationale as `set_workspaces`.
+  fn set_colors(&mut self, _colors: WorkspaceColors) {}
+}
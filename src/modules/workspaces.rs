@@ -0,0 +1,65 @@
+use eframe::egui;
+
+use super::Module;
+use crate::config::WorkspaceColors;
+use crate::glaze::{GlazeClient, Workspace};
+
+/// Renders a set of GlazeWM workspaces, highlighting the focused and
+/// visible ones. Each monitor's bar owns one instance and is handed the
+/// subset of workspaces bound to that monitor every frame. Clicking a
+/// chip sends a focus command back to GlazeWM over `glaze`.
+pub struct WorkspacesModule {
+  colors: WorkspaceColors,
+  workspaces: Vec<Workspace>,
+  glaze: GlazeClient,
+}
+
+impl WorkspacesModule {
+  pub fn new(colors: WorkspaceColors, glaze: GlazeClient) -> Self {
+    Self {
+      colors,
+      workspaces: Vec::new(),
+      glaze,
+    }
+  }
+}
+
+impl Module for WorkspacesModule {
+  fn render(&mut self, ui: &mut egui::Ui) {
+    for ws in &self.workspaces {
+      let (text_color, bg_color) = match (ws.focused, ws.visible) {
+        (true, _) => (
+          egui::Color32::WHITE,
+          egui::Color32::from_rgb(self.colors.focused[0], self.colors.focused[1], self.colors.focused[2]),
+        ),
+        (false, true) => (
+          egui::Color32::LIGHT_GRAY,
+          egui::Color32::from_rgb(self.colors.visible[0], self.colors.visible[1], self.colors.visible[2]),
+        ),
+        _ => (
+          egui::Color32::from_rgb(self.colors.idle[0], self.colors.idle[1], self.colors.idle[2]),
+          egui::Color32::TRANSPARENT,
+        ),
+      };
+
+      let frame_resp = egui::Frame::NONE
+        .fill(bg_color)
+        .corner_radius(4)
+        .inner_margin(egui::Margin::symmetric(10, 2))
+        .show(ui, |ui| ui.colored_label(text_color, &ws.name));
+
+      let resp = ui.interact(frame_resp.response.rect, ui.id().with(("workspace_chip", &ws.name)), egui::Sense::click());
+      if resp.clicked() {
+        self.glaze.send_command(format!("command focus --workspace {}", ws.name));
+      }
+    }
+  }
+
+  fn set_workspaces(&mut self, workspaces: &[Workspace]) {
+    self.workspaces = workspaces.to_vec();
+  }
+
+  fn set_colors(&mut self, colors: WorkspaceColors) {
+    self.colors = colors;
+  }
+}